@@ -1,10 +1,9 @@
 #![cfg_attr(all(not(debug_assertions), target_os = "windows"), windows_subsystem = "windows")]
 
-use std::process::{ Command, Stdio };
-use std::io::{ BufRead, BufReader };
 use serde_json;
 use serde::Deserialize;
 use tauri::{ Manager, WindowEvent };
+use tauri::api::process::{ Command, CommandEvent };
 
 #[derive(Clone, serde::Serialize)]
 struct Payload {
@@ -30,66 +29,113 @@ fn main() {
       let pipe_path = app.path_resolver().resolve_resource("bin/winemacappsPipe.sh").unwrap();
       let bin_path = app.path_resolver().resolve_resource("bin/winemacapps.sh").unwrap();
 
-      std::thread::spawn(move || {
-        let process = Command::new("/bin/bash")
-          .stdout(Stdio::piped())
-          .arg(pipe_path)
+      tauri::async_runtime::spawn(async move {
+        let (mut rx, mut _pipe_child) = Command::new("/bin/bash")
+          .args([pipe_path.to_string_lossy().as_ref()])
           .spawn()
           .expect("Failed to execute command");
-        let output = process.stdout.expect("Failed to get stdout handle");
-        let reader = BufReader::new(output);
 
-        reader
-          .lines()
-          .filter_map(|line| line.ok())
-          .for_each(move |line| {
-            let args: Vec<&str> = line.split_whitespace().collect();
-            let output = Command::new(bin_path.clone())
-              .args(args)
-              .output()
-              .expect("Failed to execute process");
-            let stdout = String::from_utf8(output.stdout).unwrap();
-            let stderr = String::from_utf8(output.stderr).unwrap();
-            let mut window_id: String = "".to_string();
-            let mut window_url: String = "".to_string();
-            let mut cmd_args: String = "".to_string();
+        while let Some(event) = rx.recv().await {
+          match event {
+            CommandEvent::Stdout(line) => {
+              let args: Vec<&str> = line.split_whitespace().collect();
+              let mut bin_args: Vec<String> = vec![bin_path.to_string_lossy().into_owned()];
+              bin_args.extend(args.into_iter().map(String::from));
+              let (mut bin_rx, _bin_child) = Command::new("/bin/bash")
+                .args(bin_args)
+                .spawn()
+                .expect("Failed to execute process");
 
-            if stderr != "" {
-              println!("{stderr}");
-            } else {
-              let stodout_str = stdout.as_str();
-              let json: CmdArgs = serde_json::from_str(stodout_str).unwrap();
-              window_id = json.config.id;
-              window_url = json.url;
-              cmd_args = stodout_str.to_string();
-            }
+              let mut stdout = String::new();
+              let mut stderr = String::new();
+              let mut failed = false;
 
-            let window = handle.get_window(window_id.as_str());
-            if let None = window {
-              tauri::WindowBuilder
-                ::new(&handle, &window_id, tauri::WindowUrl::App(window_url.into()))
-                .build()
-                .unwrap();
-            }
+              while let Some(bin_event) = bin_rx.recv().await {
+                match bin_event {
+                  CommandEvent::Stdout(bin_line) => {
+                    stdout.push_str(&bin_line);
+                    stdout.push('\n');
+                  }
+                  CommandEvent::Stderr(bin_line) => {
+                    stderr.push_str(&bin_line);
+                    stderr.push('\n');
+                  }
+                  CommandEvent::Error(err) => {
+                    println!("{err}");
+                    failed = true;
+                  }
+                  CommandEvent::Terminated(payload) => {
+                    if payload.code != Some(0) {
+                      println!(
+                        "winemacapps.sh exited with code {:?} signal {:?}",
+                        payload.code,
+                        payload.signal
+                      );
+                      failed = true;
+                    }
+                  }
+                  _ => {}
+                }
+              }
 
-            let window = handle.get_window(window_id.as_str()).unwrap();
-            let mut window_ = window.clone();
-            let window_id = window.listen("mounted", move |_| {
-              println!("App Mounted");
-              window_.emit("cmd-args", Payload { data: cmd_args.to_string().into() }).unwrap();
-            });
+              let mut window_id: String = "".to_string();
+              let mut window_url: String = "".to_string();
+              let mut cmd_args: String = "".to_string();
 
-            window_ = window.clone();
-            window.on_window_event(move |event| {
-              if let WindowEvent::Destroyed = event {
-                println!("Window destroyed");
-                window_.unlisten(window_id);
+              if failed || !stderr.is_empty() {
+                println!("{stderr}");
+                continue;
+              } else {
+                let stdout_str = stdout.trim();
+                let json: CmdArgs = serde_json::from_str(stdout_str).unwrap();
+                window_id = json.config.id;
+                window_url = json.url;
+                cmd_args = stdout_str.to_string();
               }
-            })
-          });
+
+              let window = handle.get_window(window_id.as_str());
+              if let None = window {
+                tauri::WindowBuilder
+                  ::new(&handle, &window_id, tauri::WindowUrl::App(window_url.into()))
+                  .build()
+                  .unwrap();
+              }
+
+              let window = handle.get_window(window_id.as_str()).unwrap();
+              let mut window_ = window.clone();
+              let window_id = window.listen("mounted", move |_| {
+                println!("App Mounted");
+                window_.emit("cmd-args", Payload { data: cmd_args.to_string().into() }).unwrap();
+              });
+
+              window_ = window.clone();
+              window.on_window_event(move |event| {
+                if let WindowEvent::Destroyed = event {
+                  println!("Window destroyed");
+                  window_.unlisten(window_id);
+                }
+              });
+            }
+            CommandEvent::Stderr(line) => {
+              println!("{line}");
+            }
+            CommandEvent::Error(err) => {
+              println!("{err}");
+            }
+            CommandEvent::Terminated(payload) => {
+              println!(
+                "winemacappsPipe.sh terminated: code {:?} signal {:?}",
+                payload.code,
+                payload.signal
+              );
+            }
+            _ => {}
+          }
+        }
       });
+
       Ok(())
     })
     .run(tauri::generate_context!())
     .expect("App failed");
-}
\ No newline at end of file
+}